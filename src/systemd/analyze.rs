@@ -0,0 +1,126 @@
+use std::process::Command;
+
+/// One row of the `systemd-analyze security` table: a sandboxing directive, whether it's set,
+/// and how many points it contributes to the overall exposure score.
+#[derive(Clone, Debug)]
+pub struct SecurityFinding {
+    pub setting: String,
+    pub enabled: bool,
+    pub weight:  f64,
+}
+
+/// The parsed result of running `systemd-analyze security` against a unit.
+#[derive(Clone, Debug)]
+pub struct SecurityReport {
+    pub score:    f64,
+    pub verdict:  String,
+    pub findings: Vec<SecurityFinding>,
+}
+
+/// Runs `systemd-analyze security <unit>` and parses its table into a `SecurityReport`.
+/// Returns `None` if the command couldn't be run or its output didn't parse, which is the case
+/// for unit types `systemd-analyze security` doesn't support (e.g. non-service units).
+pub fn analyze_security(unit_name: &str) -> Option<SecurityReport> {
+    let output = Command::new("systemd-analyze").arg("security").arg(unit_name).output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    parse_security_report(&stdout)
+}
+
+/// Parses the textual output of `systemd-analyze security`, which is a per-setting table
+/// (leading `✓`/`✗` glyph, setting name, description, weighted contribution) followed by a
+/// summary line naming the overall exposure level and predicate.
+fn parse_security_report(output: &str) -> Option<SecurityReport> {
+    let mut findings = Vec::new();
+    let mut score = None;
+    let mut verdict = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+
+        if let Some(finding) = parse_finding_line(trimmed) {
+            findings.push(finding);
+            continue;
+        }
+
+        if let Some((parsed_score, parsed_verdict)) = parse_summary_line(trimmed) {
+            score = Some(parsed_score);
+            verdict = Some(parsed_verdict);
+        }
+    }
+
+    Some(SecurityReport { score: score?, verdict: verdict?, findings })
+}
+
+/// Parses a single table row, e.g. `✗ PrivateNetwork=                 ...description...   0.5`.
+fn parse_finding_line(line: &str) -> Option<SecurityFinding> {
+    let mut chars = line.chars();
+    let enabled = match chars.next()? {
+        '✓' => true,
+        '✗' => false,
+        _ => return None,
+    };
+
+    let rest = chars.as_str().trim();
+    let setting = rest.split_whitespace().next()?.to_owned();
+    let weight = rest.split_whitespace().last()?.parse().unwrap_or(0.0);
+
+    Some(SecurityFinding { setting, enabled, weight })
+}
+
+/// Parses the trailing summary line, e.g.
+/// `→ Overall exposure level for foo.service: 2.3 MEDIUM`.
+fn parse_summary_line(line: &str) -> Option<(f64, String)> {
+    let colon = line.rfind(':')?;
+    let mut fields = line[colon + 1..].split_whitespace();
+    let score = fields.next()?.parse().ok()?;
+    let verdict = fields.next()?.to_owned();
+    Some((score, verdict))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finding_line_with_multibyte_glyph_does_not_panic() {
+        let finding = parse_finding_line("✗ PrivateNetwork=          Service has access to the host's network   0.5").unwrap();
+        assert_eq!(finding.setting, "PrivateNetwork=");
+        assert!(!finding.enabled);
+        assert_eq!(finding.weight, 0.5);
+    }
+
+    #[test]
+    fn finding_line_enabled() {
+        let finding = parse_finding_line("✓ ProtectKernelModules=    Service cannot load or read kernel modules   0.0").unwrap();
+        assert_eq!(finding.setting, "ProtectKernelModules=");
+        assert!(finding.enabled);
+    }
+
+    #[test]
+    fn non_table_lines_are_ignored() {
+        assert!(parse_finding_line("  NAME                DESCRIPTION                EXPOSURE").is_none());
+    }
+
+    #[test]
+    fn summary_line_parses_score_and_verdict() {
+        let (score, verdict) =
+            parse_summary_line("→ Overall exposure level for foo.service: 2.3 MEDIUM").unwrap();
+        assert_eq!(score, 2.3);
+        assert_eq!(verdict, "MEDIUM");
+    }
+
+    #[test]
+    fn full_report_parses_findings_and_summary() {
+        let output = "\
+  NAME                    DESCRIPTION                                EXPOSURE
+✗ PrivateNetwork=         Service has access to the host's network   0.5
+✓ ProtectKernelModules=   Service cannot load or read kernel modules  0.0
+
+→ Overall exposure level for foo.service: 2.3 MEDIUM
+";
+        let report = parse_security_report(output).unwrap();
+        assert_eq!(report.score, 2.3);
+        assert_eq!(report.verdict, "MEDIUM");
+        assert_eq!(report.findings.len(), 2);
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use dbus::arg::{PropMap, RefArg};
+use dbus::blocking::Connection;
+use dbus::message::MatchRule;
+use dbus::{Message, Path};
+use notify_rust::Notification;
+
+use super::journal;
+use super::UnitState;
+
+const DESTINATION: &str = "org.freedesktop.systemd1";
+const PATH: &str = "/org/freedesktop/systemd1";
+const MANAGER_IFACE: &str = "org.freedesktop.systemd1.Manager";
+const UNIT_IFACE: &str = "org.freedesktop.systemd1.Unit";
+const PROPERTIES_IFACE: &str = "org.freedesktop.DBus.Properties";
+
+/// Watches a user-chosen set of units over D-Bus and fires a desktop notification whenever one
+/// of them transitions into a failed state.
+pub struct UnitWatcher {
+    watched:   Arc<Mutex<HashSet<String>>>,
+    stop_flag: Arc<AtomicBool>,
+    handle:    Option<JoinHandle<()>>,
+}
+
+impl UnitWatcher {
+    /// Creates a watcher, restoring the watch-list persisted from a previous run.
+    pub fn new() -> UnitWatcher {
+        UnitWatcher {
+            watched:   Arc::new(Mutex::new(load_watch_list())),
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            handle:    None,
+        }
+    }
+
+    /// Adds a unit to the watch-list and persists the change.
+    pub fn watch(&self, unit_name: &str) {
+        let mut watched = self.watched.lock().unwrap();
+        watched.insert(unit_name.to_owned());
+        save_watch_list(&watched);
+    }
+
+    /// Removes a unit from the watch-list and persists the change.
+    pub fn unwatch(&self, unit_name: &str) {
+        let mut watched = self.watched.lock().unwrap();
+        watched.remove(unit_name);
+        save_watch_list(&watched);
+    }
+
+    /// Returns whether `unit_name` is currently on the watch-list.
+    pub fn is_watched(&self, unit_name: &str) -> bool {
+        self.watched.lock().unwrap().contains(unit_name)
+    }
+
+    /// Subscribes to PID1's `PropertiesChanged`/`JobRemoved` signals on a background thread and
+    /// starts reacting to watched units entering a bad state.
+    pub fn start(&mut self) {
+        let watched_for_properties = self.watched.clone();
+        let watched_for_jobs = self.watched.clone();
+        let stop_flag = self.stop_flag.clone();
+
+        let handle = thread::spawn(move || {
+            let connection = match Connection::new_system() {
+                Ok(connection) => connection,
+                // No system bus to watch from; give up quietly rather than spin.
+                Err(_) => return,
+            };
+
+            // systemd only emits per-unit PropertiesChanged/JobRemoved signals to clients that
+            // have asked for them via the manager's Subscribe() call.
+            let manager = connection.with_proxy(DESTINATION, PATH, Duration::from_secs(5));
+            let subscribed: Result<(), dbus::Error> = manager.method_call(MANAGER_IFACE, "Subscribe", ());
+            if subscribed.is_err() {
+                return;
+            }
+
+            // Unit state changes arrive as PropertiesChanged on each unit's own object path, not
+            // on the manager's path, so the match rule is scoped by interface/member only and
+            // the unit is recovered from the signal's path instead.
+            let properties_rule = MatchRule::new_signal(PROPERTIES_IFACE, "PropertiesChanged");
+            let properties_added = connection.add_match(
+                properties_rule,
+                move |(interface, changed, _invalidated): (String, PropMap, Vec<String>), _, message| {
+                    handle_properties_changed(message, &interface, &changed, &watched_for_properties);
+                    true
+                },
+            );
+
+            let job_rule = MatchRule::new_signal(MANAGER_IFACE, "JobRemoved");
+            let job_added = connection.add_match(
+                job_rule,
+                move |(_job_id, _job, unit_name, result): (u32, Path, String, String), _, _| {
+                    handle_job_removed(&unit_name, &result, &watched_for_jobs);
+                    true
+                },
+            );
+
+            if properties_added.is_err() || job_added.is_err() {
+                return;
+            }
+
+            while !stop_flag.load(Ordering::SeqCst) {
+                let _ = connection.process(Duration::from_millis(500));
+            }
+        });
+
+        self.handle = Some(handle);
+    }
+
+    /// Signals the background thread to exit and waits for it to finish.
+    pub fn stop(&mut self) {
+        self.stop_flag.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Default for UnitWatcher {
+    fn default() -> UnitWatcher { UnitWatcher::new() }
+}
+
+/// Reacts to a `PropertiesChanged` signal: ignores anything that isn't the unit interface's
+/// `ActiveState`, recovers the unit name from the signal's object path, and notifies if it's on
+/// the watch-list and just went bad.
+fn handle_properties_changed(
+    message: &Message,
+    interface: &str,
+    changed: &PropMap,
+    watched: &Arc<Mutex<HashSet<String>>>,
+) {
+    if interface != UNIT_IFACE {
+        return;
+    }
+
+    let unit_name = match unit_name_from_path(message.path()) {
+        Some(name) => name,
+        None => return,
+    };
+
+    if !watched.lock().unwrap().contains(&unit_name) {
+        return;
+    }
+
+    if let Some(active_state) = changed.get("ActiveState").and_then(|v| v.0.as_str()) {
+        if map_active_state(active_state) == UnitState::Bad {
+            notify_failure(&unit_name);
+        }
+    }
+}
+
+/// Reacts to a `JobRemoved` signal: notifies if the unit the job was for is on the watch-list
+/// and the job's result was a failure.
+fn handle_job_removed(unit_name: &str, result: &str, watched: &Arc<Mutex<HashSet<String>>>) {
+    if !watched.lock().unwrap().contains(unit_name) {
+        return;
+    }
+
+    if result == "failed" {
+        notify_failure(unit_name);
+    }
+}
+
+/// Recovers the unit name from a unit object path (e.g.
+/// `/org/freedesktop/systemd1/unit/foo_2eservice`), reversing the `_XX` hex-escaping systemd
+/// applies to any byte that isn't alphanumeric when folding a unit name into a bus path.
+fn unit_name_from_path(path: Option<&Path>) -> Option<String> {
+    let segment = path?.to_string();
+    let segment = segment.rsplit('/').next()?;
+
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut chars = segment.chars();
+    while let Some(c) = chars.next() {
+        if c == '_' {
+            let hi = chars.next()?;
+            let lo = chars.next()?;
+            bytes.push(u8::from_str_radix(&format!("{}{}", hi, lo), 16).ok()?);
+        } else {
+            bytes.push(c as u8);
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+/// Maps the `ActiveState` string carried by a D-Bus signal onto the existing `UnitState` enum so
+/// the watcher can reuse the same bad/good vocabulary as the rest of the app, rather than
+/// introducing a second state enum just for live transitions. `SubState` is not consulted.
+fn map_active_state(active_state: &str) -> UnitState {
+    match active_state {
+        "failed"                         => UnitState::Bad,
+        "active" | "reloading"           => UnitState::Enabled,
+        "inactive" | "deactivating"      => UnitState::Disabled,
+        "activating"                     => UnitState::Transient,
+        _                                 => UnitState::Disabled,
+    }
+}
+
+/// Fires a freedesktop desktop notification naming the unit and the last few journal lines that
+/// led up to the failure.
+fn notify_failure(unit_name: &str) {
+    let tail = journal::read_journal(unit_name, 5, 7);
+    let body = tail.iter().map(|entry| entry.message.as_str()).collect::<Vec<_>>().join("\n");
+
+    let _ = Notification::new()
+        .summary(&format!("{} has failed", unit_name))
+        .body(&body)
+        .show();
+}
+
+/// Path to the file used to persist the watch-list across restarts.
+fn watch_list_path() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(std::env::temp_dir);
+    path.push("systemd-manager");
+    path.push("watched_units");
+    path
+}
+
+/// Loads the persisted watch-list, one unit name per line, or an empty set if none exists yet.
+fn load_watch_list() -> HashSet<String> {
+    File::open(watch_list_path())
+        .map(|file| BufReader::new(file).lines().filter_map(Result::ok).collect())
+        .unwrap_or_default()
+}
+
+/// Persists the watch-list, one unit name per line.
+fn save_watch_list(watched: &HashSet<String>) {
+    let path = watch_list_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+
+    if let Ok(mut file) = File::create(path) {
+        for unit_name in watched {
+            let _ = writeln!(file, "{}", unit_name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_failed_to_bad() {
+        assert_eq!(map_active_state("failed"), UnitState::Bad);
+    }
+
+    #[test]
+    fn maps_active_to_enabled() {
+        assert_eq!(map_active_state("active"), UnitState::Enabled);
+    }
+
+    #[test]
+    fn unescapes_unit_path_segment() {
+        let path = Path::new("/org/freedesktop/systemd1/unit/foo_2eservice").unwrap();
+        assert_eq!(unit_name_from_path(Some(&path)), Some("foo.service".to_owned()));
+    }
+}
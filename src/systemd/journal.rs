@@ -0,0 +1,130 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use systemd::journal::{self, Journal, JournalRecord, JournalWaitResult};
+
+/// A single entry read back from the journal for a specific unit.
+#[derive(Clone, Debug)]
+pub struct JournalEntry {
+    pub timestamp: SystemTime,
+    pub priority:  u8,
+    pub message:   String,
+}
+
+impl JournalEntry {
+    /// Builds an entry from a raw `JournalRecord`, defaulting fields that are missing or
+    /// malformed rather than failing the whole read.
+    fn from_record(record: &JournalRecord) -> JournalEntry {
+        let priority = record.get("PRIORITY").and_then(|p| p.parse().ok()).unwrap_or(7);
+
+        let timestamp = record
+            .get("__REALTIME_TIMESTAMP")
+            .and_then(|t| t.parse::<u64>().ok())
+            .map(|usec| UNIX_EPOCH + Duration::from_micros(usec))
+            .unwrap_or(UNIX_EPOCH);
+
+        let message = record.get("MESSAGE").cloned().unwrap_or_default();
+
+        JournalEntry { timestamp, priority, message }
+    }
+}
+
+/// Opens a journal handle matched to `unit_name`, seeked to the tail.
+pub(crate) fn open_for_unit(unit_name: &str) -> Option<Journal> {
+    let mut reader = journal::OpenOptions::default().open().ok()?;
+    reader.match_add("_SYSTEMD_UNIT", unit_name).ok()?;
+    reader.seek_tail().ok()?;
+    Some(reader)
+}
+
+/// Reads up to `max_entries` journal records for `unit_name`, walking backward from the tail,
+/// and drops anything less severe than `min_priority` (0 = emerg, 7 = debug) so opening a noisy
+/// unit doesn't block the UI reading the entire boot.
+pub fn read_journal(unit_name: &str, max_entries: usize, min_priority: u8) -> Vec<JournalEntry> {
+    let mut reader = match open_for_unit(unit_name) {
+        Some(reader) => reader,
+        None => return Vec::new(),
+    };
+
+    let mut entries = Vec::with_capacity(max_entries);
+    while entries.len() < max_entries {
+        match reader.previous_entry() {
+            Ok(Some(record)) => {
+                let entry = JournalEntry::from_record(&record);
+                if entry.priority <= min_priority {
+                    entries.push(entry);
+                }
+            }
+            // Either the read failed or we've walked off the start of the journal.
+            _ => break,
+        }
+    }
+
+    entries
+}
+
+/// How long to block on the journal fd between polls while following. Kept short so `stop()`
+/// doesn't leave the background thread hanging around for long after the user switches units.
+const FOLLOW_POLL_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Streams newly-appended journal entries for a unit into a channel, without ever re-reading
+/// history that's already been displayed.
+pub struct JournalFollower {
+    stop_flag: Arc<AtomicBool>,
+    handle:    Option<JoinHandle<()>>,
+}
+
+impl JournalFollower {
+    /// Starts following `unit_name`'s journal on a background thread. Returns the follower
+    /// (used to `stop()` it) and a receiver that yields each new `JournalEntry` as it arrives.
+    pub fn start(unit_name: &str) -> (JournalFollower, Receiver<JournalEntry>) {
+        let (sender, receiver) = mpsc::channel();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let thread_stop_flag = stop_flag.clone();
+        let unit_name = unit_name.to_owned();
+
+        let handle = thread::spawn(move || {
+            let mut reader = match open_for_unit(&unit_name) {
+                Some(reader) => reader,
+                // Nothing to follow if the journal couldn't be opened; exit quietly.
+                None => return,
+            };
+
+            // `seek_tail()` leaves the cursor positioned after the last existing entry, not on
+            // it, so the first `next_entry()` would otherwise re-read that last entry — the
+            // same one the one-shot `get_journal` snapshot already showed. Consuming it here via
+            // `previous_entry()` anchors the cursor on it, so the wait loop below only ever
+            // yields entries appended after this point.
+            let _ = reader.previous_entry();
+
+            while !thread_stop_flag.load(Ordering::SeqCst) {
+                match reader.wait(Some(FOLLOW_POLL_TIMEOUT)) {
+                    Ok(JournalWaitResult::Nop) | Ok(JournalWaitResult::Invalidate) => continue,
+                    Ok(JournalWaitResult::Append) => {
+                        while let Ok(Some(record)) = reader.next_entry() {
+                            if sender.send(JournalEntry::from_record(&record)).is_err() {
+                                // Receiver dropped; nothing left to do but stop.
+                                return;
+                            }
+                        }
+                    }
+                    // The fd or the journal itself went away; there's nothing left to follow.
+                    Err(_) => return,
+                }
+            }
+        });
+
+        (JournalFollower { stop_flag, handle: Some(handle) }, receiver)
+    }
+
+    /// Signals the background thread to stop and waits for it to tear down its journal handle.
+    pub fn stop(mut self) {
+        self.stop_flag.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
@@ -1,11 +1,16 @@
 pub mod analyze;
 pub mod dbus;
+pub mod journal;
 pub mod systemctl;
+pub mod unit_file;
 
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
-use std::process::Command;
+use std::sync::mpsc;
+
+pub use self::journal::{JournalEntry, JournalFollower};
+pub use self::unit_file::UnitFile;
 
 #[derive(Clone, Debug)]
 pub struct SystemdUnit {
@@ -31,13 +36,23 @@ impl SystemdUnit {
             .ok().unwrap_or_default()
     }
 
-    /// Obtains the journal log for the given unit.
-    pub fn get_journal(&self) -> String {
-        Command::new("journalctl").arg("-b").arg("-r").arg("-u").arg(&self.name).output().ok()
-            // Collect the output of the journal as a `String`
-            .and_then(|output| String::from_utf8(output.stdout).ok())
-            // Return the contents of the journal, otherwise return an error message
-            .unwrap_or_else(|| format!("Unable to read the journal entry for {}.", self.name))
+    /// Obtains up to `max_entries` journal records for this unit, most recent first, dropping
+    /// anything less severe than `min_priority` (0 = emerg, 7 = debug).
+    pub fn get_journal(&self, max_entries: usize, min_priority: u8) -> Vec<JournalEntry> {
+        journal::read_journal(&self.name, max_entries, min_priority)
+    }
+
+    /// Starts following this unit's journal, returning a follower (used to stop it) and a
+    /// channel that yields each new entry as it's appended, for use alongside a one-shot
+    /// `get_journal` snapshot when the GUI keeps the log pane live.
+    pub fn follow_journal(&self) -> (JournalFollower, mpsc::Receiver<JournalEntry>) {
+        JournalFollower::start(&self.name)
+    }
+
+    /// Parses this unit's file into a structured, editable `UnitFile` rather than leaving
+    /// callers to scan `get_info()`'s raw text for directives themselves.
+    pub fn unit_file(&self) -> UnitFile {
+        UnitFile::parse(&self.get_info())
     }
 }
 
@@ -83,12 +98,3 @@ impl UnitState {
         }
     }
 }
-
-/// Obtain the description from the unit file and return it.
-pub fn get_unit_description(info: &str) -> Option<&str> {
-    info.lines()
-        // Find the line that starts with `Description=`.
-        .find(|x| x.starts_with("Description="))
-        // Split the line and return the latter half that contains the description.
-        .map(|description| description.split_at(12).1)
-}
@@ -0,0 +1,229 @@
+use std::fmt;
+
+/// One logical line of a parsed unit file, preserved in file order so `to_string()` can
+/// round-trip comments and layout rather than just reconstructing sections from scratch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Line {
+    Section(String),
+    Directive { key: String, value: String },
+    Comment(String),
+    Blank,
+}
+
+/// Classifies one already-joined logical line and appends it to `lines`, updating
+/// `current_section` when it's a section header. Shared by the main parse loop and by the
+/// end-of-input flush for a final line with no trailing newline to complete it.
+fn push_parsed_line(lines: &mut Vec<Line>, current_section: &mut String, line: String) {
+    let trimmed = line.trim();
+
+    if trimmed.is_empty() {
+        lines.push(Line::Blank);
+    } else if trimmed.starts_with('#') || trimmed.starts_with(';') {
+        lines.push(Line::Comment(line));
+    } else if trimmed.starts_with('[') && trimmed.ends_with(']') {
+        *current_section = trimmed[1..trimmed.len() - 1].to_owned();
+        lines.push(Line::Section(current_section.clone()));
+    } else if let Some(eq) = trimmed.find('=') {
+        let key = trimmed[..eq].trim().to_owned();
+        let value = trimmed[eq + 1..].trim().to_owned();
+        lines.push(Line::Directive { key, value });
+    } else {
+        // Not a recognized directive; keep the line verbatim so round-tripping doesn't silently
+        // drop it.
+        lines.push(Line::Comment(line));
+    }
+}
+
+/// A parsed systemd unit file: an ordered list of `[Section]` headers and the key/value
+/// directives beneath them. Duplicate keys are kept in order, since systemd treats repeating a
+/// directive (e.g. multiple `ExecStartPre=`) as appending rather than overwriting.
+#[derive(Clone, Debug, Default)]
+pub struct UnitFile {
+    lines: Vec<Line>,
+}
+
+impl UnitFile {
+    /// Parses unit file contents into sections and directives, joining any line ending in a
+    /// trailing `\` with the line that follows it, as systemd does.
+    pub fn parse(contents: &str) -> UnitFile {
+        let mut lines = Vec::new();
+        let mut current_section = String::new();
+        let mut pending = String::new();
+
+        for raw_line in contents.lines() {
+            if !pending.is_empty() {
+                pending.push_str(raw_line.trim_start());
+            } else {
+                pending.push_str(raw_line);
+            }
+
+            if let Some(continued) = pending.strip_suffix('\\') {
+                pending = continued.trim_end().to_owned();
+                pending.push(' ');
+                continue;
+            }
+
+            let line = std::mem::take(&mut pending);
+            push_parsed_line(&mut lines, &mut current_section, line);
+        }
+
+        // A trailing `\` on the file's last line has no following line to join with; flush
+        // whatever was accumulated instead of silently dropping it.
+        if !pending.is_empty() {
+            push_parsed_line(&mut lines, &mut current_section, pending);
+        }
+
+        UnitFile { lines }
+    }
+
+    /// Returns the first value of `key` within `section`, if present.
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.get_all(section, key).into_iter().next()
+    }
+
+    /// Returns every value of `key` within `section`, in file order, since systemd treats a
+    /// repeated directive as multi-valued rather than as an overwrite.
+    pub fn get_all(&self, section: &str, key: &str) -> Vec<&str> {
+        let mut values = Vec::new();
+        let mut current_section = "";
+
+        for line in &self.lines {
+            match line {
+                Line::Section(name) => current_section = name,
+                Line::Directive { key: k, value } if current_section == section && k == key => {
+                    values.push(value.as_str());
+                }
+                _ => {}
+            }
+        }
+
+        values
+    }
+
+    /// Replaces the first occurrence of `key` within `section` with `value`, removing any other
+    /// occurrences so the directive ends up single-valued. Appends a new section and directive
+    /// if `section`/`key` don't already exist.
+    pub fn set(&mut self, section: &str, key: &str, value: &str) {
+        let mut current_section = "";
+        let first = self.lines.iter().position(|line| match line {
+            Line::Section(name) => {
+                current_section = name;
+                false
+            }
+            Line::Directive { key: k, .. } => current_section == section && k == key,
+            _ => false,
+        });
+
+        // Nothing before `first` is removed, so it is still the slot of the first occurrence.
+        self.remove(section, key);
+        match first {
+            Some(index) => self.lines.insert(index, Line::Directive { key: key.to_owned(), value: value.to_owned() }),
+            None => self.append(section, key, value),
+        }
+    }
+
+    /// Appends `key=value` to `section` without disturbing any existing occurrences, for
+    /// directives systemd treats as multi-valued (e.g. `ExecStartPre=`).
+    pub fn append(&mut self, section: &str, key: &str, value: &str) {
+        let directive = Line::Directive { key: key.to_owned(), value: value.to_owned() };
+
+        if let Some(end) = self.section_end(section) {
+            self.lines.insert(end, directive);
+        } else {
+            self.lines.push(Line::Section(section.to_owned()));
+            self.lines.push(directive);
+        }
+    }
+
+    /// Removes every occurrence of `key` within `section`.
+    pub fn remove(&mut self, section: &str, key: &str) {
+        let mut current_section = String::new();
+        self.lines.retain(|line| match line {
+            Line::Section(name) => {
+                current_section = name.clone();
+                true
+            }
+            Line::Directive { key: k, .. } => !(current_section == section && k == key),
+            _ => true,
+        });
+    }
+
+    /// Index just past the last line belonging to `section`, so a new directive can be inserted
+    /// at the end of that section's block. Returns `None` if the section doesn't exist.
+    fn section_end(&self, section: &str) -> Option<usize> {
+        let start = self.lines.iter().position(|line| matches!(line, Line::Section(name) if name == section))?;
+        let end = self.lines[start + 1..]
+            .iter()
+            .position(|line| matches!(line, Line::Section(_)))
+            .map(|offset| start + 1 + offset)
+            .unwrap_or(self.lines.len());
+        Some(end)
+    }
+
+    /// The unit's `Description=` directive from `[Unit]`, if set.
+    pub fn description(&self) -> Option<&str> { self.get("Unit", "Description") }
+
+    /// The service's `ExecStart=` directive from `[Service]`, if set.
+    pub fn exec_start(&self) -> Option<&str> { self.get("Service", "ExecStart") }
+}
+
+/// Reconstructs the unit file text, preserving comments and ordering, so `to_string()` gives
+/// back something safe to write back to disk.
+impl fmt::Display for UnitFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in &self.lines {
+            match line {
+                Line::Section(name) => writeln!(f, "[{}]", name)?,
+                Line::Directive { key, value } => writeln!(f, "{}={}", key, value)?,
+                Line::Comment(text) => writeln!(f, "{}", text)?,
+                Line::Blank => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_description_and_exec_start() {
+        let unit = UnitFile::parse("[Unit]\nDescription=My Service\n\n[Service]\nExecStart=/bin/true\n");
+        assert_eq!(unit.description(), Some("My Service"));
+        assert_eq!(unit.exec_start(), Some("/bin/true"));
+    }
+
+    #[test]
+    fn joins_trailing_backslash_continuations() {
+        let unit = UnitFile::parse("[Service]\nExecStart=/bin/echo one \\\n    two \\\n    three\n");
+        assert_eq!(unit.get("Service", "ExecStart"), Some("/bin/echo one two three"));
+    }
+
+    #[test]
+    fn flushes_a_final_line_ending_in_a_continuation() {
+        // No trailing newline, and the last physical line still ends in `\`.
+        let unit = UnitFile::parse("[Service]\nExecStart=/bin/echo one \\");
+        assert_eq!(unit.get("Service", "ExecStart"), Some("/bin/echo one"));
+    }
+
+    #[test]
+    fn keeps_duplicate_directives_in_order() {
+        let unit = UnitFile::parse("[Service]\nExecStartPre=/bin/one\nExecStartPre=/bin/two\n");
+        assert_eq!(unit.get_all("Service", "ExecStartPre"), vec!["/bin/one", "/bin/two"]);
+    }
+
+    #[test]
+    fn set_replaces_all_occurrences_with_one() {
+        let mut unit = UnitFile::parse("[Service]\nExecStartPre=/bin/one\nExecStartPre=/bin/two\n");
+        unit.set("Service", "ExecStartPre", "/bin/three");
+        assert_eq!(unit.get_all("Service", "ExecStartPre"), vec!["/bin/three"]);
+    }
+
+    #[test]
+    fn round_trips_comments_and_ordering() {
+        let contents = "# a comment\n[Unit]\nDescription=My Service\n\n[Service]\nExecStart=/bin/true\n";
+        let unit = UnitFile::parse(contents);
+        assert_eq!(unit.to_string(), contents);
+    }
+}